@@ -4,6 +4,15 @@ use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf, sync::Mutex, time::Duration};
 use tauri::{AppHandle, Emitter, Manager, State};
 
+fn default_clipboard_clear_after_secs() -> u32 { 30 }
+fn default_stt_provider() -> String { "groq".into() }
+fn default_stt_model() -> String { "whisper-large-v3".into() }
+fn default_stt_base_url() -> String { "https://api.groq.com/openai/v1".into() }
+fn default_nlp_provider() -> String { "gemini".into() }
+fn default_nlp_model() -> String { "gemini-1.5-flash-latest".into() }
+fn default_nlp_base_url() -> String { "https://api.openai.com/v1".into() }
+fn default_hotkey() -> String { "Alt+Space".into() }
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppSettings {
@@ -13,6 +22,8 @@ pub struct AppSettings {
     pub no_save: bool,
     pub encrypt_temp_files: bool,
     pub auto_clear_clipboard: bool,
+    #[serde(default = "default_clipboard_clear_after_secs")]
+    pub clipboard_clear_after_secs: u32,
     pub clear_all_on_exit: bool,
     pub mask_strength: String,
     pub mask_phone: bool,
@@ -40,6 +51,20 @@ pub struct AppSettings {
     pub preserve_original_proper_nouns: bool,
     pub no_summary_or_embellishment: bool,
     pub custom_replace_rules: Vec<ReplaceRule>,
+    #[serde(default = "default_stt_provider")]
+    pub stt_provider: String,
+    #[serde(default = "default_stt_model")]
+    pub stt_model: String,
+    #[serde(default = "default_stt_base_url")]
+    pub stt_base_url: String,
+    #[serde(default = "default_nlp_provider")]
+    pub nlp_provider: String,
+    #[serde(default = "default_nlp_model")]
+    pub nlp_model: String,
+    #[serde(default = "default_nlp_base_url")]
+    pub nlp_base_url: String,
+    #[serde(default = "default_hotkey")]
+    pub hotkey: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,12 +77,13 @@ pub struct ReplaceRule {
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
-            settings_version: 1,
+            settings_version: CURRENT_SETTINGS_VERSION,
             security_master_mode: "standard".into(),
             enable_gemini: true,
             no_save: true,
             encrypt_temp_files: true,
             auto_clear_clipboard: true,
+            clipboard_clear_after_secs: 30,
             clear_all_on_exit: true,
             mask_strength: "standard".into(),
             mask_phone: true,
@@ -71,8 +97,8 @@ impl Default for AppSettings {
             region_preference: "nearest".into(),
             use_byo_key: true,
             save_email_display_name: false,
-            short_lived_session: true,
-            clear_tokens_on_logout: true,
+            short_lived_session: false,
+            clear_tokens_on_logout: false,
             enable_error_logs: false,
             enable_usage_stats: false,
             auto_delete_logs_after_days: 90,
@@ -85,21 +111,42 @@ impl Default for AppSettings {
             preserve_original_proper_nouns: true,
             no_summary_or_embellishment: true,
             custom_replace_rules: vec![],
+            stt_provider: "groq".into(),
+            stt_model: "whisper-large-v3".into(),
+            stt_base_url: "https://api.groq.com/openai/v1".into(),
+            nlp_provider: "gemini".into(),
+            nlp_model: "gemini-1.5-flash-latest".into(),
+            nlp_base_url: "https://api.openai.com/v1".into(),
+            hotkey: "Alt+Space".into(),
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct Keys { pub groq_api_key: Option<String>, pub gemini_api_key: Option<String> }
+pub struct Keys { pub groq_api_key: Option<String>, pub gemini_api_key: Option<String>, pub openai_api_key: Option<String> }
+
+/// Which store actually served the keys returned by `keys_get`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretBackend { Keychain, File }
+
+impl Default for SecretBackend {
+    fn default() -> Self { SecretBackend::File }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct KeysPresence { pub has_groq: bool, pub has_gemini: bool }
+pub struct KeysPresence { pub has_groq: bool, pub has_gemini: bool, pub has_openai: bool, pub backend: SecretBackend }
 
-#[derive(Default)]
 struct AppState {
     settings: Mutex<AppSettings>,
     settings_path: Mutex<Option<PathBuf>>,
     recording_active: Mutex<bool>,
+    /// Bumped on every `clipboard_set`; a pending auto-clear timer compares against
+    /// the value it captured at schedule time and skips clearing if it no longer matches.
+    clipboard_epoch: Mutex<u64>,
+    /// AES-GCM key generated fresh per app launch, used only to encrypt temp audio
+    /// at rest when `encrypt_temp_files` is on. Never persisted.
+    session_cipher: aes_gcm::Aes256Gcm,
 }
 
 fn save_settings_to_disk(path: &PathBuf, s: &AppSettings) -> Result<(), String> {
@@ -108,8 +155,67 @@ fn save_settings_to_disk(path: &PathBuf, s: &AppSettings) -> Result<(), String>
     fs::write(path, data).map_err(|e| e.to_string())
 }
 
+/// The schema version `load_settings_from_disk` migrates up to.
+const CURRENT_SETTINGS_VERSION: u32 = 2;
+
+type SettingsMigration = fn(&mut serde_json::Value);
+
+/// Ordered chain of schema migrations. `MIGRATIONS[i]` upgrades a document from
+/// version `i + 1` to version `i + 2`; applying `MIGRATIONS[version - 1..]` in
+/// order brings any older file up to `CURRENT_SETTINGS_VERSION`.
+static MIGRATIONS: &[SettingsMigration] = &[migrate_v1_to_v2];
+
+/// v1 settings.json files predate the STT/NLP provider and hotkey settings added since.
+/// NOTE: this is a pure default-seed, not a real transform — every key below already carries
+/// a matching `#[serde(default = "...")]` on `AppSettings`, so `from_value` would fill each of
+/// them with the same value even if this function were deleted. The re-persisted file spells
+/// those keys out either way, because `load_settings_from_disk` saves the fully deserialized
+/// `AppSettings`; this step only makes the seeded values explicit in the migrated document
+/// before deserialization. If a future version actually needs to rename,
+/// drop, or reshape a key, that's the bar for what belongs in `MIGRATIONS[i]` — don't assume this
+/// one sets a precedent for "seed-only" steps being the norm.
+fn migrate_v1_to_v2(v: &mut serde_json::Value) {
+    let Some(obj) = v.as_object_mut() else { return };
+    const SEEDS: &[(&str, fn() -> serde_json::Value)] = &[
+        ("clipboardClearAfterSecs", || serde_json::json!(default_clipboard_clear_after_secs())),
+        ("sttProvider", || serde_json::json!(default_stt_provider())),
+        ("sttModel", || serde_json::json!(default_stt_model())),
+        ("sttBaseUrl", || serde_json::json!(default_stt_base_url())),
+        ("nlpProvider", || serde_json::json!(default_nlp_provider())),
+        ("nlpModel", || serde_json::json!(default_nlp_model())),
+        ("nlpBaseUrl", || serde_json::json!(default_nlp_base_url())),
+        ("hotkey", || serde_json::json!(default_hotkey())),
+    ];
+    for (key, default) in SEEDS {
+        obj.entry(*key).or_insert_with(default);
+    }
+}
+
+/// Parses the settings file as raw JSON first so the `settingsVersion` field can be
+/// read before the schema is known, runs every migration between that version and
+/// `CURRENT_SETTINGS_VERSION`, then persists and deserializes the upgraded document.
+/// A version at or beyond `CURRENT_SETTINGS_VERSION` (including an unrecognized future
+/// one) is loaded best-effort without running any migration over it.
 fn load_settings_from_disk(path: &PathBuf) -> Option<AppSettings> {
-    fs::read(path).ok().and_then(|d| serde_json::from_slice(&d).ok())
+    let data = fs::read(path).ok()?;
+    let mut value: serde_json::Value = serde_json::from_slice(&data).ok()?;
+    let version = value.get("settingsVersion").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+    if version == 0 || version >= CURRENT_SETTINGS_VERSION {
+        return serde_json::from_value(value).ok();
+    }
+    for migration in &MIGRATIONS[(version as usize - 1)..] {
+        migration(&mut value);
+    }
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("settingsVersion".into(), serde_json::json!(CURRENT_SETTINGS_VERSION));
+    }
+    match serde_json::from_value::<AppSettings>(value) {
+        Ok(settings) => {
+            let _ = save_settings_to_disk(path, &settings);
+            Some(settings)
+        }
+        Err(_) => Some(AppSettings::default()),
+    }
 }
 
 fn secrets_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
@@ -117,17 +223,84 @@ fn secrets_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     Ok(dir.join("secrets.json"))
 }
 
+/// Service name the three key accounts are namespaced under in the OS keychain.
+const KEYRING_SERVICE: &str = "com.app.dictation.secrets";
+const KEYRING_ACCOUNTS: &[&str] = &["groq_api_key", "gemini_api_key", "openai_api_key"];
+
+fn keyring_entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, account).map_err(|e| e.to_string())
+}
+
+fn keys_field_mut(keys: &mut Keys, account: &str) -> &mut Option<String> {
+    match account {
+        "groq_api_key" => &mut keys.groq_api_key,
+        "gemini_api_key" => &mut keys.gemini_api_key,
+        _ => &mut keys.openai_api_key,
+    }
+}
+
+fn keys_field<'a>(keys: &'a Keys, account: &str) -> &'a Option<String> {
+    match account {
+        "groq_api_key" => &keys.groq_api_key,
+        "gemini_api_key" => &keys.gemini_api_key,
+        _ => &keys.openai_api_key,
+    }
+}
+
+fn read_keys_from_keyring() -> Keys {
+    let mut keys = Keys::default();
+    for account in KEYRING_ACCOUNTS {
+        let value = keyring_entry(account).ok().and_then(|e| e.get_password().ok());
+        *keys_field_mut(&mut keys, account) = value;
+    }
+    keys
+}
+
+fn write_keys_to_keyring(keys: &Keys) -> Result<(), String> {
+    for account in KEYRING_ACCOUNTS {
+        let entry = keyring_entry(account)?;
+        match keys_field(keys, account) {
+            Some(v) if !v.is_empty() => entry.set_password(v).map_err(|e| e.to_string())?,
+            _ => match entry.delete_password() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) => return Err(e.to_string()),
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Reads keys from the OS keychain first; the plaintext `secrets.json` file is kept
+/// only as an explicit fallback for keys written before the keychain migration.
+fn read_keys(app: &AppHandle) -> (Keys, SecretBackend) {
+    let keyring_keys = read_keys_from_keyring();
+    if keyring_keys.groq_api_key.is_some() || keyring_keys.gemini_api_key.is_some() || keyring_keys.openai_api_key.is_some() {
+        return (keyring_keys, SecretBackend::Keychain);
+    }
+    let file_keys = secrets_path(app).ok()
+        .and_then(|p| fs::read(&p).ok())
+        .and_then(|b| serde_json::from_slice::<Keys>(&b).ok())
+        .unwrap_or_default();
+    (file_keys, SecretBackend::File)
+}
+
 fn read_keys_from_file(app: &AppHandle) -> Result<Keys, String> {
-    let path = secrets_path(app)?;
-    Ok(fs::read(&path).ok().and_then(|b| serde_json::from_slice::<Keys>(&b).ok()).unwrap_or_default())
+    Ok(read_keys(app).0)
 }
 
+/// Writes keys to the keychain; falls back to the legacy chmod-0600 JSON file only
+/// when the platform has no usable keychain backend.
 fn write_keys_to_file(app: &AppHandle, keys: &Keys) -> Result<(), String> {
+    if write_keys_to_keyring(keys).is_ok() {
+        // Keychain write succeeded: drop any legacy plaintext copy so it can't drift out of sync.
+        if let Ok(path) = secrets_path(app) { let _ = fs::remove_file(&path); }
+        return Ok(());
+    }
     let path = secrets_path(app)?;
     if let Some(parent) = path.parent() { fs::create_dir_all(parent).map_err(|e| e.to_string())?; }
-    // If both keys are None or empty, remove file
     if keys.groq_api_key.as_ref().map(|s| s.is_empty()).unwrap_or(true)
-        && keys.gemini_api_key.as_ref().map(|s| s.is_empty()).unwrap_or(true) {
+        && keys.gemini_api_key.as_ref().map(|s| s.is_empty()).unwrap_or(true)
+        && keys.openai_api_key.as_ref().map(|s| s.is_empty()).unwrap_or(true) {
         let _ = fs::remove_file(&path);
         return Ok(())
     }
@@ -146,8 +319,8 @@ fn write_keys_to_file(app: &AppHandle, keys: &Keys) -> Result<(), String> {
 
 #[tauri::command]
 fn keys_get(app: AppHandle) -> Result<KeysPresence, String> {
-    let k = read_keys_from_file(&app)?;
-    Ok(KeysPresence { has_groq: k.groq_api_key.is_some(), has_gemini: k.gemini_api_key.is_some() })
+    let (k, backend) = read_keys(&app);
+    Ok(KeysPresence { has_groq: k.groq_api_key.is_some(), has_gemini: k.gemini_api_key.is_some(), has_openai: k.openai_api_key.is_some(), backend })
 }
 
 #[tauri::command]
@@ -155,6 +328,7 @@ fn keys_set(app: AppHandle, keys: Keys) -> Result<(), String> {
     let mut current = read_keys_from_file(&app)?;
     if let Some(v) = keys.groq_api_key { if !v.is_empty() { current.groq_api_key = Some(v); } }
     if let Some(v) = keys.gemini_api_key { if !v.is_empty() { current.gemini_api_key = Some(v); } }
+    if let Some(v) = keys.openai_api_key { if !v.is_empty() { current.openai_api_key = Some(v); } }
     write_keys_to_file(&app, &current)
 }
 
@@ -164,11 +338,20 @@ fn keys_clear(app: AppHandle, which: Option<String>) -> Result<(), String> {
     match which.as_deref() {
         Some("groq") => current.groq_api_key = None,
         Some("gemini") => current.gemini_api_key = None,
-        _ => { current.groq_api_key = None; current.gemini_api_key = None; }
+        Some("openai") => current.openai_api_key = None,
+        _ => { current.groq_api_key = None; current.gemini_api_key = None; current.openai_api_key = None; }
     }
     write_keys_to_file(&app, &current)
 }
 
+/// Wipes every stored key from the keychain (and any legacy file), for the frontend to
+/// call on logout. The exit handler in `run()` performs the same wipe on its own when
+/// `clear_tokens_on_logout` or `short_lived_session` is enabled.
+#[tauri::command]
+fn keys_wipe_session(app: AppHandle) -> Result<(), String> {
+    write_keys_to_file(&app, &Keys::default())
+}
+
 #[tauri::command]
 fn settings_get(state: State<AppState>) -> Result<AppSettings, String> {
     Ok(state.settings.lock().unwrap().clone())
@@ -177,9 +360,16 @@ fn settings_get(state: State<AppState>) -> Result<AppSettings, String> {
 #[tauri::command]
 fn settings_update(app: AppHandle, state: State<AppState>, partial: serde_json::Value) -> Result<AppSettings, String> {
     let mut s = state.settings.lock().unwrap();
+    let previous_hotkey = s.hotkey.clone();
     let mut v = serde_json::to_value(&*s).map_err(|e| e.to_string())?;
     merge(&mut v, &partial);
-    *s = serde_json::from_value(v).map_err(|e| e.to_string())?;
+    let candidate: AppSettings = serde_json::from_value(v).map_err(|e| e.to_string())?;
+    // Register before persisting: an invalid/conflicting accelerator must fail here,
+    // not leave a broken hotkey saved to disk that `run()` then fails to register next launch.
+    if candidate.hotkey != previous_hotkey {
+        register_hotkey(&app, &candidate.hotkey)?;
+    }
+    *s = candidate;
     if let Some(path) = state.settings_path.lock().unwrap().clone() {
         let _ = save_settings_to_disk(&path, &s);
     }
@@ -199,8 +389,44 @@ fn merge(a: &mut serde_json::Value, b: &serde_json::Value) {
     }
 }
 
+/// Flips `recording_active` and emits the same `ptt:stateChanged` event as `ptt_start`/`ptt_stop`;
+/// this is what the global hotkey calls so it behaves identically to a press-and-release pair.
+fn ptt_toggle(app: &AppHandle) {
+    let state: State<AppState> = app.state();
+    let mut active = state.recording_active.lock().unwrap();
+    *active = !*active;
+    let _ = app.emit("ptt:stateChanged", if *active { "recording" } else { "processing" });
+}
+
+/// Unregisters any previously bound global shortcut and registers `accelerator` in its place,
+/// wired to `ptt_toggle`. Returns a descriptive error on an unparsable accelerator or conflict.
+fn register_hotkey(app: &AppHandle, accelerator: &str) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+    let shortcut = Shortcut::new(accelerator).map_err(|e| format!("invalid hotkey '{accelerator}': {e}"))?;
+    let gs = app.global_shortcut();
+    gs.unregister_all().map_err(|e| e.to_string())?;
+    let handle = app.clone();
+    gs.register(shortcut, move || ptt_toggle(&handle))
+        .map_err(|e| format!("failed to register hotkey '{accelerator}': {e}"))
+}
+
+/// The single source of truth for the push-to-talk hotkey: parses/validates the supplied
+/// accelerator (falling back to the persisted `hotkey` setting), registers it, and persists
+/// the choice so it survives restart.
 #[tauri::command]
-async fn ptt_register(_app: AppHandle, _hotkey: Option<String>) -> Result<(), String> { Ok(()) }
+async fn ptt_register(app: AppHandle, state: State<'_, AppState>, hotkey: Option<String>) -> Result<(), String> {
+    let accelerator = match hotkey {
+        Some(h) => h,
+        None => state.settings.lock().unwrap().hotkey.clone(),
+    };
+    register_hotkey(&app, &accelerator)?;
+    let mut settings = state.settings.lock().unwrap();
+    settings.hotkey = accelerator;
+    if let Some(path) = state.settings_path.lock().unwrap().clone() {
+        let _ = save_settings_to_disk(&path, &settings);
+    }
+    Ok(())
+}
 
 #[tauri::command]
 async fn ptt_start(app: AppHandle, state: State<AppState>) -> Result<(), String> {
@@ -216,13 +442,42 @@ async fn ptt_stop(app: AppHandle, state: State<AppState>) -> Result<(), String>
         ; Ok(())
 }
 
+/// Spawns a background countdown that clears the clipboard after `delay_secs`, unless
+/// `clipboard_set`/`clipboard_clear` bumped `clipboard_epoch` again in the meantime.
+/// Emits `clipboard:clearCountdown` once a second so the UI can show the countdown.
+fn schedule_clipboard_clear(app: AppHandle, epoch: u64, delay_secs: u32) {
+    tauri::async_runtime::spawn(async move {
+        for remaining in (1..=delay_secs.max(1)).rev() {
+            let _ = app.emit("clipboard:clearCountdown", remaining);
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        let state: State<AppState> = app.state();
+        let unchanged = *state.clipboard_epoch.lock().unwrap() == epoch;
+        if unchanged {
+            let _ = tauri_plugin_clipboard_manager::clear(&app);
+            let _ = app.emit("clipboard:cleared", ());
+        }
+    });
+}
+
 #[tauri::command]
-async fn clipboard_set(app: AppHandle, text: String) -> Result<(), String> {
-    tauri_plugin_clipboard_manager::set_text(&app, text).map_err(|e| e.to_string())
+async fn clipboard_set(app: AppHandle, state: State<'_, AppState>, text: String) -> Result<(), String> {
+    tauri_plugin_clipboard_manager::set_text(&app, text).map_err(|e| e.to_string())?;
+    let epoch = {
+        let mut epoch = state.clipboard_epoch.lock().unwrap();
+        *epoch += 1;
+        *epoch
+    };
+    let settings = state.settings.lock().unwrap().clone();
+    if settings.auto_clear_clipboard {
+        schedule_clipboard_clear(app, epoch, settings.clipboard_clear_after_secs);
+    }
+    Ok(())
 }
 
 #[tauri::command]
-async fn clipboard_clear(app: AppHandle) -> Result<(), String> {
+async fn clipboard_clear(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    *state.clipboard_epoch.lock().unwrap() += 1;
     tauri_plugin_clipboard_manager::clear(&app).map_err(|e| e.to_string())
 }
 
@@ -245,79 +500,332 @@ async fn input_paste(_app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Where the bundled ggml whisper model is expected to live: a single well-known file
+/// under the app data dir, like `secrets.json` is under the app config dir.
+fn local_whisper_model_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_data_dir().map_err(|e| e.to_string())?.join("models").join("ggml-base.bin"))
+}
+
+/// A loaded whisper.cpp model, ready to transcribe 16kHz mono f32 PCM.
+struct WhisperModel(whisper_rs::WhisperContext);
+
+impl WhisperModel {
+    fn load(path: &PathBuf) -> Result<Self, String> {
+        let path_str = path.to_str().ok_or("non-utf8 model path")?;
+        whisper_rs::WhisperContext::new_with_params(path_str, whisper_rs::WhisperContextParameters::default())
+            .map(WhisperModel)
+            .map_err(|e| e.to_string())
+    }
+
+    fn transcribe(&self, pcm: &[f32]) -> Result<String, String> {
+        let mut state = self.0.create_state().map_err(|e| e.to_string())?;
+        let mut params = whisper_rs::FullParams::new(whisper_rs::SamplingStrategy::Greedy { best_of: 1 });
+        params.set_print_progress(false);
+        params.set_print_special(false);
+        params.set_print_realtime(false);
+        state.full(params, pcm).map_err(|e| e.to_string())?;
+        let segments = state.full_n_segments().map_err(|e| e.to_string())?;
+        let mut text = String::new();
+        for i in 0..segments {
+            text.push_str(&state.full_get_segment_text(i).map_err(|e| e.to_string())?);
+        }
+        Ok(text.trim().to_string())
+    }
+}
+
+/// A speech-to-text backend: given raw audio bytes, returns the transcribed text.
+/// Implemented once per remote API shape so `stt_transcribe_once` stays provider-agnostic.
+#[async_trait::async_trait]
+trait SttProvider: Send + Sync {
+    async fn process(&self, audio_bytes: Vec<u8>) -> Result<String, String>;
+}
+
+struct GroqStt { api_key: String, model: String }
+
+impl GroqStt {
+    fn from_settings(settings: &AppSettings, api_key: String) -> Self {
+        Self { api_key, model: settings.stt_model.clone() }
+    }
+}
+
+#[async_trait::async_trait]
+impl SttProvider for GroqStt {
+    async fn process(&self, audio_bytes: Vec<u8>) -> Result<String, String> {
+        let client = reqwest::Client::builder().timeout(Duration::from_secs(60)).build().map_err(|e| e.to_string())?;
+        let part = reqwest::multipart::Part::bytes(audio_bytes).file_name("audio.webm").mime_str("audio/webm").map_err(|e| e.to_string())?;
+        let form = reqwest::multipart::Form::new().part("file", part).text("model", self.model.clone());
+        let resp = client
+            .post("https://api.groq.com/openai/v1/audio/transcriptions")
+            .bearer_auth(&self.api_key)
+            .multipart(form)
+            .send().await.map_err(|e| e.to_string())?;
+        if !resp.status().is_success() { return Err(format!("STT HTTP {}", resp.status())); }
+        #[derive(Deserialize)]
+        struct SttResp { text: String }
+        let data: SttResp = resp.json().await.map_err(|e| e.to_string())?;
+        Ok(data.text)
+    }
+}
+
+/// Any endpoint that speaks the OpenAI `audio/transcriptions` multipart API, including self-hosted ones.
+struct OpenAiCompatibleStt { api_key: String, base_url: String, model: String }
+
+impl OpenAiCompatibleStt {
+    fn from_settings(settings: &AppSettings, api_key: String) -> Self {
+        Self { api_key, base_url: settings.stt_base_url.clone(), model: settings.stt_model.clone() }
+    }
+}
+
+#[async_trait::async_trait]
+impl SttProvider for OpenAiCompatibleStt {
+    async fn process(&self, audio_bytes: Vec<u8>) -> Result<String, String> {
+        let client = reqwest::Client::builder().timeout(Duration::from_secs(60)).build().map_err(|e| e.to_string())?;
+        let part = reqwest::multipart::Part::bytes(audio_bytes).file_name("audio.webm").mime_str("audio/webm").map_err(|e| e.to_string())?;
+        let form = reqwest::multipart::Form::new().part("file", part).text("model", self.model.clone());
+        let url = format!("{}/audio/transcriptions", self.base_url.trim_end_matches('/'));
+        let resp = client.post(url).bearer_auth(&self.api_key).multipart(form).send().await.map_err(|e| e.to_string())?;
+        if !resp.status().is_success() { return Err(format!("STT HTTP {}", resp.status())); }
+        #[derive(Deserialize)]
+        struct SttResp { text: String }
+        let data: SttResp = resp.json().await.map_err(|e| e.to_string())?;
+        Ok(data.text)
+    }
+}
+
+/// Which speech-to-text backend a given transcription request should use.
+enum SttBackend {
+    Remote(Box<dyn SttProvider>),
+    Local(WhisperModel),
+}
+
+impl SttBackend {
+    fn from_settings(app: &AppHandle, settings: &AppSettings) -> Result<Self, String> {
+        let api_key = match settings.stt_provider.as_str() {
+            "openai_compatible" => std::env::var("OPENAI_API_KEY").ok().or_else(|| read_keys_from_file(app).ok().and_then(|k| k.openai_api_key)),
+            _ => std::env::var("GROQ_API_KEY").ok().or_else(|| read_keys_from_file(app).ok().and_then(|k| k.groq_api_key)),
+        };
+        if !settings.offline_mode {
+            if let Some(api_key) = api_key {
+                let provider: Box<dyn SttProvider> = match settings.stt_provider.as_str() {
+                    "openai_compatible" => Box::new(OpenAiCompatibleStt::from_settings(settings, api_key)),
+                    _ => Box::new(GroqStt::from_settings(settings, api_key)),
+                };
+                return Ok(SttBackend::Remote(provider));
+            }
+        }
+        let model_path = local_whisper_model_path(app)?;
+        if !model_path.exists() {
+            return Err("local transcription (offline mode or no STT API key) requires a local speech model, but none is installed".into());
+        }
+        Ok(SttBackend::Local(WhisperModel::load(&model_path)?))
+    }
+}
+
+/// Decodes a browser-recorded WebM/Opus clip into 16kHz mono f32 PCM, the format whisper.cpp expects.
+///
+/// Symphonia's default codec registry has no Opus decoder, so Symphonia is used only to demux
+/// the WebM container and hand us the track's raw Opus packets; libopus (via the `opus` crate)
+/// decodes those packets directly, requesting 16kHz output so no separate resampling is needed.
+fn decode_webm_to_pcm16k_mono(bytes: &[u8]) -> Result<Vec<f32>, String> {
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes.to_vec())), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension("webm");
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| e.to_string())?;
+    let mut format = probed.format;
+    let track = format.default_track().ok_or("webm clip has no audio track")?.clone();
+    let track_id = track.id;
+    let channel_count = track.codec_params.channels.map(|c| c.count()).unwrap_or(1).clamp(1, 2);
+    let opus_channels = if channel_count == 2 { opus::Channels::Stereo } else { opus::Channels::Mono };
+    let mut decoder = opus::Decoder::new(16_000, opus_channels).map_err(|e| e.to_string())?;
+
+    // 120ms is the longest Opus packet libopus can decode (1,920 samples per channel at 16kHz);
+    // size the scratch buffer for that maximum so every packet fits regardless of how the
+    // encoder chunked the clip.
+    let mut scratch = vec![0f32; 1_920 * channel_count];
+    let mut mono = Vec::new();
+    while let Ok(packet) = format.next_packet() {
+        if packet.track_id() != track_id { continue; }
+        let decoded_samples = decoder.decode_float(&packet.data, &mut scratch, false).map_err(|e| e.to_string())?;
+        let frame = &scratch[..decoded_samples * channel_count];
+        if channel_count > 1 {
+            mono.extend(frame.chunks(channel_count).map(|f| f.iter().sum::<f32>() / channel_count as f32));
+        } else {
+            mono.extend_from_slice(frame);
+        }
+    }
+    Ok(mono)
+}
+
+/// Reports whether a local offline model is installed, mirroring `keys_get`'s role
+/// of letting the UI reflect readiness without exposing any secret material.
 #[tauri::command]
-async fn stt_transcribe_once(_app: AppHandle, settings: AppSettings, audio_b64: String) -> Result<String, String> {
-    if settings.offline_mode { return Ok(String::new()); }
-    let api_key = std::env::var("GROQ_API_KEY").ok()
-        .or_else(|| {
-            let path = _app.path().app_config_dir().ok()?.join("secrets.json");
-            fs::read(&path).ok().and_then(|b| serde_json::from_slice::<Keys>(&b).ok()).and_then(|k| k.groq_api_key)
-        });
-    if api_key.is_none() { return Ok("(demo: STT disabled; set GROQ_API_KEY)".into()); }
-    let api_key = api_key.unwrap();
-    let client = reqwest::Client::builder().timeout(Duration::from_secs(60)).build().map_err(|e| e.to_string())?;
-    let audio_bytes = base64::decode(audio_b64).map_err(|e| e.to_string())?;
-    let part = reqwest::multipart::Part::bytes(audio_bytes).file_name("audio.webm").mime_str("audio/webm").map_err(|e| e.to_string())?;
-    let form = reqwest::multipart::Form::new()
-        .part("file", part)
-        .text("model", "whisper-large-v3");
-    let resp = client
-        .post("https://api.groq.com/openai/v1/audio/transcriptions")
-        .bearer_auth(api_key)
-        .multipart(form)
-        .send().await.map_err(|e| e.to_string())?;
-    if !resp.status().is_success() { return Err(format!("STT HTTP {}", resp.status())); }
-    #[derive(Deserialize)]
-    struct SttResp { text: String }
-    let data: SttResp = resp.json().await.map_err(|e| e.to_string())?;
-    Ok(data.text)
+fn stt_offline_model_present(app: AppHandle) -> Result<bool, String> {
+    Ok(local_whisper_model_path(&app)?.exists())
+}
+
+/// Where temp audio clips are written when `no_save` is off, optionally encrypted at rest.
+fn temp_audio_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_data_dir().map_err(|e| e.to_string())?.join("temp_audio"))
+}
+
+/// Persists a recorded clip to disk when `no_save` is off, encrypting it with the
+/// per-session AES-GCM key from `AppState` when `encrypt_temp_files` is on.
+fn persist_temp_audio(app: &AppHandle, state: &State<AppState>, settings: &AppSettings, bytes: &[u8]) -> Result<(), String> {
+    use aes_gcm::aead::{Aead, OsRng};
+    use aes_gcm::AeadCore;
+    if settings.no_save { return Ok(()); }
+    let dir = temp_audio_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let stamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_err(|e| e.to_string())?.as_nanos();
+    if settings.encrypt_temp_files {
+        let nonce = aes_gcm::Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = state.session_cipher.encrypt(&nonce, bytes).map_err(|e| e.to_string())?;
+        let mut data = nonce.to_vec();
+        data.extend(ciphertext);
+        fs::write(dir.join(format!("clip-{stamp}.webm.enc")), data).map_err(|e| e.to_string())
+    } else {
+        fs::write(dir.join(format!("clip-{stamp}.webm")), bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// Overwrites every temp audio clip with zeros before deleting it, so `clear_all_on_exit`
+/// doesn't just unlink files whose contents may still be recoverable on disk.
+fn zeroize_temp_audio(app: &AppHandle) {
+    let Ok(dir) = temp_audio_dir(app) else { return };
+    let Ok(entries) = fs::read_dir(&dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if let Ok(meta) = fs::metadata(&path) {
+            let _ = fs::write(&path, vec![0u8; meta.len() as usize]);
+        }
+        let _ = fs::remove_file(&path);
+    }
 }
 
 #[tauri::command]
-async fn nlp_gemini_format(_app: AppHandle, settings: AppSettings, text: String) -> Result<String, String> {
+async fn stt_transcribe_once(app: AppHandle, state: State<'_, AppState>, settings: AppSettings, audio_b64: String) -> Result<String, String> {
+    let audio_bytes = base64::decode(&audio_b64).map_err(|e| e.to_string())?;
+    persist_temp_audio(&app, &state, &settings, &audio_bytes)?;
+    match SttBackend::from_settings(&app, &settings)? {
+        SttBackend::Local(model) => {
+            let pcm = decode_webm_to_pcm16k_mono(&audio_bytes)?;
+            model.transcribe(&pcm)
+        }
+        SttBackend::Remote(provider) => provider.process(audio_bytes).await,
+    }
+}
+
+/// A text formatter: given the freeform transcription, returns the cleaned-up text.
+/// Implemented once per chat API shape so `nlp_gemini_format` stays provider-agnostic.
+#[async_trait::async_trait]
+trait TextFormatter: Send + Sync {
+    async fn process(&self, settings: &AppSettings, text: String) -> Result<String, String>;
+}
+
+struct GeminiFormatter { api_key: String }
+
+impl GeminiFormatter {
+    fn from_settings(_settings: &AppSettings, api_key: String) -> Self { Self { api_key } }
+}
+
+#[async_trait::async_trait]
+impl TextFormatter for GeminiFormatter {
+    async fn process(&self, settings: &AppSettings, text: String) -> Result<String, String> {
+        let model = &settings.nlp_model;
+        let system_instructions = build_gemini_instructions(settings);
+        #[derive(Serialize)]
+        struct ContentPart { text: String }
+        #[derive(Serialize)]
+        struct Content { role: String, parts: Vec<ContentPart> }
+        #[derive(Serialize)]
+        struct Req { contents: Vec<Content>, system_instruction: Option<Content> }
+        let req = Req {
+            contents: vec![Content { role: "user".into(), parts: vec![ContentPart { text }] }],
+            system_instruction: Some(Content { role: "system".into(), parts: vec![ContentPart { text: system_instructions }] }),
+        };
+        let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}", model, self.api_key);
+        let client = reqwest::Client::new();
+        let resp = client.post(url).json(&req).send().await.map_err(|e| e.to_string())?;
+        if !resp.status().is_success() { return Err(format!("Gemini HTTP {}", resp.status())); }
+        #[derive(Deserialize)]
+        struct CandidatePart { text: Option<String> }
+        #[derive(Deserialize)]
+        struct CandidateContent { parts: Option<Vec<CandidatePart>> }
+        #[derive(Deserialize)]
+        struct Candidate { content: Option<CandidateContent> }
+        #[derive(Deserialize)]
+        struct Resp { candidates: Option<Vec<Candidate>> }
+        let data: Resp = resp.json().await.map_err(|e| e.to_string())?;
+        Ok(data
+            .candidates
+            .and_then(|mut c| c.pop())
+            .and_then(|c| c.content)
+            .and_then(|c| c.parts)
+            .and_then(|mut p| p.into_iter().find_map(|p| p.text))
+            .unwrap_or_default())
+    }
+}
+
+/// Any endpoint that speaks the OpenAI `chat/completions` API, including self-hosted ones.
+struct OpenAiCompatibleFormatter { api_key: String }
+
+impl OpenAiCompatibleFormatter {
+    fn from_settings(_settings: &AppSettings, api_key: String) -> Self { Self { api_key } }
+}
+
+#[async_trait::async_trait]
+impl TextFormatter for OpenAiCompatibleFormatter {
+    async fn process(&self, settings: &AppSettings, text: String) -> Result<String, String> {
+        let system_instructions = build_gemini_instructions(settings);
+        #[derive(Serialize)]
+        struct Message { role: String, content: String }
+        #[derive(Serialize)]
+        struct Req { model: String, messages: Vec<Message> }
+        let req = Req {
+            model: settings.nlp_model.clone(),
+            messages: vec![
+                Message { role: "system".into(), content: system_instructions },
+                Message { role: "user".into(), content: text },
+            ],
+        };
+        let url = format!("{}/chat/completions", settings.nlp_base_url.trim_end_matches('/'));
+        let client = reqwest::Client::new();
+        let resp = client.post(url).bearer_auth(&self.api_key).json(&req).send().await.map_err(|e| e.to_string())?;
+        if !resp.status().is_success() { return Err(format!("NLP HTTP {}", resp.status())); }
+        #[derive(Deserialize)]
+        struct Choice { message: ChoiceMessage }
+        #[derive(Deserialize)]
+        struct ChoiceMessage { content: Option<String> }
+        #[derive(Deserialize)]
+        struct Resp { choices: Vec<Choice> }
+        let data: Resp = resp.json().await.map_err(|e| e.to_string())?;
+        Ok(data.choices.into_iter().next().and_then(|c| c.message.content).unwrap_or_default())
+    }
+}
+
+#[tauri::command]
+async fn nlp_gemini_format(app: AppHandle, settings: AppSettings, text: String) -> Result<String, String> {
     if !settings.enable_gemini || settings.offline_mode { return Ok(text); }
-    let key = std::env::var("GEMINI_API_KEY").ok()
-        .or_else(|| {
-            let path = _app.path().app_config_dir().ok()?.join("secrets.json");
-            fs::read(&path).ok().and_then(|b| serde_json::from_slice::<Keys>(&b).ok()).and_then(|k| k.gemini_api_key)
-        });
-    if key.is_none() { return Ok(text); }
-    let key = key.unwrap();
-    let model = "gemini-1.5-flash-latest";
-    let system_instructions = build_gemini_instructions(&settings);
-    #[derive(Serialize)]
-    struct ContentPart { text: String }
-    #[derive(Serialize)]
-    struct Content { role: String, parts: Vec<ContentPart> }
-    #[derive(Serialize)]
-    struct Req { contents: Vec<Content>, system_instruction: Option<Content> }
-    let req = Req {
-        contents: vec![Content { role: "user".into(), parts: vec![ContentPart { text }] }],
-        system_instruction: Some(Content { role: "system".into(), parts: vec![ContentPart { text: system_instructions }] }),
+    let key = match settings.nlp_provider.as_str() {
+        "openai_compatible" => std::env::var("OPENAI_API_KEY").ok().or_else(|| read_keys_from_file(&app).ok().and_then(|k| k.openai_api_key)),
+        _ => std::env::var("GEMINI_API_KEY").ok().or_else(|| read_keys_from_file(&app).ok().and_then(|k| k.gemini_api_key)),
     };
-    let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}", model, key);
-    let client = reqwest::Client::new();
-    let resp = client.post(url).json(&req).send().await.map_err(|e| e.to_string())?;
-    if !resp.status().is_success() { return Err(format!("Gemini HTTP {}", resp.status())); }
-    #[derive(Deserialize)]
-    struct CandidatePart { text: Option<String> }
-    #[derive(Deserialize)]
-    struct CandidateContent { parts: Option<Vec<CandidatePart>> }
-    #[derive(Deserialize)]
-    struct Candidate { content: Option<CandidateContent> }
-    #[derive(Deserialize)]
-    struct Resp { candidates: Option<Vec<Candidate>> }
-    let data: Resp = resp.json().await.map_err(|e| e.to_string())?;
-    let out = data
-        .candidates
-        .and_then(|mut c| c.pop())
-        .and_then(|c| c.content)
-        .and_then(|c| c.parts)
-        .and_then(|mut p| p.into_iter().find_map(|p| p.text))
-        .unwrap_or_default();
-    Ok(out)
+    let Some(key) = key else { return Ok(text) };
+    let formatter: Box<dyn TextFormatter> = match settings.nlp_provider.as_str() {
+        "openai_compatible" => Box::new(OpenAiCompatibleFormatter::from_settings(&settings, key)),
+        _ => Box::new(GeminiFormatter::from_settings(&settings, key)),
+    };
+    formatter.process(&settings, text).await
 }
 
+/// Builds the provider-agnostic system prompt shared by every `TextFormatter` implementation.
 fn build_gemini_instructions(s: &AppSettings) -> String {
     let mut lines = vec![
         "あなたは入力テキストを自然な文に整形します。".to_string(),
@@ -340,6 +848,43 @@ static PHONE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?x)
 ").unwrap());
 static NUMBER_SEQ_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d{6,}").unwrap());
 
+/// Honorific suffixes that mark the end of a Japanese personal name.
+const NAME_HONORIFICS: &[&str] = &["さん", "様", "氏", "くん", "ちゃん", "先生", "部長", "課長"];
+
+/// Matches a kanji (1-4 chars) or katakana run immediately followed by an honorific,
+/// capturing the name portion separately from the honorific so the latter survives masking.
+static NAME_RE: Lazy<Regex> = Lazy::new(|| {
+    let honorifics = NAME_HONORIFICS.join("|");
+    Regex::new(&format!(r"(\p{{Han}}{{1,4}}|\p{{Katakana}}{{2,}})({})", honorifics)).unwrap()
+});
+
+/// Matches a Japanese address span: a prefecture/city lead-in through the administrative
+/// suffixes that typically close out a street address (丁目/番地/号).
+static ADDRESS_RE: Lazy<Regex> = Lazy::new(|| {
+    // The prefecture lead-in is optional: plenty of addresses are written starting at the
+    // city/ward token (e.g. `渋谷区道玄坂1丁目`) with the prefecture omitted entirely.
+    Regex::new(r"(?:[\p{Han}ぁ-んァ-ヶー]{1,8}[都道府県])?[\p{Han}ぁ-んァ-ヶー0-9０-９]{1,12}[市区町村][\p{Han}ぁ-んァ-ヶー0-9０-９\-ー]{0,20}(?:[0-9０-９]{1,4}丁目)?(?:[0-9０-９]{1,4}番地?)?(?:[0-9０-９]{1,4}号)?").unwrap()
+});
+
+/// Swaps every occurrence of a whitelisted word for a sentinel placeholder so the
+/// masking regexes below can't match inside it, returning the tokens needed to undo it.
+fn protect_whitelist(input: &str, whitelist: &[String]) -> (String, Vec<(String, String)>) {
+    let mut out = input.to_string();
+    let mut placeholders = Vec::new();
+    for (i, word) in whitelist.iter().enumerate() {
+        if word.is_empty() || !out.contains(word.as_str()) { continue; }
+        let token = format!("\u{0}WL{}\u{0}", i);
+        out = out.replace(word.as_str(), &token);
+        placeholders.push((token, word.clone()));
+    }
+    (out, placeholders)
+}
+
+fn restore_whitelist(mut out: String, placeholders: &[(String, String)]) -> String {
+    for (token, word) in placeholders { out = out.replace(token.as_str(), word); }
+    out
+}
+
 fn apply_custom_rules(s: &AppSettings, input: &str) -> String {
     let mut out = input.to_string();
     for rule in &s.custom_replace_rules {
@@ -351,9 +896,11 @@ fn apply_custom_rules(s: &AppSettings, input: &str) -> String {
 
 #[tauri::command]
 fn mask_text(settings: AppSettings, input: String) -> Result<String, String> {
-    let mut out = input;
+    let (protected, placeholders) = protect_whitelist(&input, &settings.whitelist_words);
+    let mut out = protected;
     if settings.enable_dlp_scan {
-        let has_sensitive = EMAIL_RE.is_match(&out) || PHONE_RE.is_match(&out) || NUMBER_SEQ_RE.is_match(&out);
+        let has_sensitive = EMAIL_RE.is_match(&out) || PHONE_RE.is_match(&out) || NUMBER_SEQ_RE.is_match(&out)
+            || NAME_RE.is_match(&out) || ADDRESS_RE.is_match(&out);
         match settings.dlp_action.as_str() {
             "block" if has_sensitive => return Err("DLP block".into()),
             "warn" if has_sensitive => { /* could emit warn */ }
@@ -363,8 +910,10 @@ fn mask_text(settings: AppSettings, input: String) -> Result<String, String> {
     if settings.mask_email { out = EMAIL_RE.replace_all(&out, "＜メール＞").into_owned(); }
     if settings.mask_phone { out = PHONE_RE.replace_all(&out, "＜電話番号＞").into_owned(); }
     if settings.mask_numbers { out = NUMBER_SEQ_RE.replace_all(&out, "＜数列＞").into_owned(); }
-    // TODO: address, names (requires locale resources)
+    if settings.mask_names { out = NAME_RE.replace_all(&out, "＜氏名＞$2").into_owned(); }
+    if settings.mask_address { out = ADDRESS_RE.replace_all(&out, "＜住所＞").into_owned(); }
     out = apply_custom_rules(&settings, &out);
+    out = restore_whitelist(out, &placeholders);
     Ok(out)
 }
 
@@ -382,27 +931,22 @@ pub fn run() {
             let cfg_dir = app.path().app_config_dir().map_err(|e| format!("path error: {e}"))?;
             let settings_path = cfg_dir.join("settings.json");
             let current = load_settings_from_disk(&settings_path).unwrap_or_default();
+            let hotkey = current.hotkey.clone();
+            use aes_gcm::{aead::OsRng, Aes256Gcm, KeyInit};
             let state = AppState {
                 settings: Mutex::new(current),
                 settings_path: Mutex::new(Some(settings_path)),
                 recording_active: Mutex::new(false),
+                clipboard_epoch: Mutex::new(0),
+                session_cipher: Aes256Gcm::new(&Aes256Gcm::generate_key(OsRng)),
             };
             app.manage(state);
 
-            // Register global hotkey (Alt+Space) as toggle start/stop
-            use tauri_plugin_global_shortcut::Shortcut;
-            let handle = app.handle();
-            let _ = app.global_shortcut().register(Shortcut::new("Alt+Space").unwrap(), move || {
-                let s: State<AppState> = handle.state();
-                let active = *s.recording_active.lock().unwrap();
-                if !active {
-                    let _ = handle.emit("ptt:stateChanged", "recording");
-                    *s.recording_active.lock().unwrap() = true;
-                } else {
-                    let _ = handle.emit("ptt:stateChanged", "processing");
-                    *s.recording_active.lock().unwrap() = false;
-                }
-            });
+            // Register the persisted (or default) push-to-talk hotkey; ptt_register and
+            // settings_update re-register it afterwards, both through register_hotkey.
+            if let Err(e) = register_hotkey(&app.handle(), &hotkey) {
+                eprintln!("failed to register push-to-talk hotkey '{hotkey}': {e}");
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -415,12 +959,31 @@ pub fn run() {
             clipboard_clear,
             input_paste,
             stt_transcribe_once,
+            stt_offline_model_present,
             nlp_gemini_format,
             mask_text,
             keys_get,
             keys_set,
             keys_clear,
+            keys_wipe_session,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // When `clear_all_on_exit` is on, wipe the clipboard and temp audio clips
+            // before the app actually quits. This does not touch persisted keys — see
+            // the `clear_tokens_on_logout`/`short_lived_session` check below for that.
+            if let tauri::RunEvent::ExitRequested { .. } = event {
+                let settings = { let state: State<AppState> = app.state(); state.settings.lock().unwrap().clone() };
+                if settings.clear_all_on_exit {
+                    let _ = tauri_plugin_clipboard_manager::clear(app);
+                    zeroize_temp_audio(app);
+                }
+                // Keys are session/logout material, not tied to `clear_all_on_exit`: wipe them
+                // here whenever the user has opted into short-lived sessions or logout clearing.
+                if settings.clear_tokens_on_logout || settings.short_lived_session {
+                    let _ = write_keys_to_file(app, &Keys::default());
+                }
+            }
+        });
 }